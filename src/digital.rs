@@ -1,22 +1,69 @@
 //! Digital I/O
 
+/// Error type trait for digital I/O traits
+///
+/// Implementors specify the error type returned by their fallible
+/// operations via this supertrait, so that implementations which
+/// cannot fail can use `core::convert::Infallible`.
+pub trait ErrorType {
+    /// Error type
+    type Error;
+}
+
 /// Single digital output pin
-pub trait OutputPin {
+pub trait OutputPin: ErrorType {
     /// Sets the pin low
-    fn set_low(&mut self);
+    fn set_low(&mut self) -> Result<(), Self::Error>;
 
     /// Sets the pin high
-    fn set_high(&mut self);
+    fn set_high(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Output pin drive mode
+#[cfg(feature = "unproven")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveMode {
+    /// Push-pull output: actively drives both the high and low level
+    PushPull,
+    /// Open-drain output: actively drives the low level, leaving the
+    /// high level floating (relying on an external or internal pull-up)
+    OpenDrain,
+}
+
+/// Output pin drive strength
+#[cfg(feature = "unproven")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveStrength {
+    /// Standard drive strength
+    Standard,
+    /// High drive strength
+    High,
+}
+
+/// Output pin whose drive mode and strength can be configured
+///
+/// This lets drivers for shared-bus signals like I2C SDA/SCL or a
+/// wired-OR interrupt line portably request open-drain behavior (and,
+/// where supported, a specific drive strength). Implementations that
+/// cannot honor a requested [`DriveMode`] or [`DriveStrength`] must
+/// return an error rather than silently ignoring the request.
+#[cfg(feature = "unproven")]
+pub trait DrivePin: OutputPin {
+    /// Set the output drive mode (push-pull vs open-drain)
+    fn set_drive_mode(&mut self, mode: DriveMode) -> Result<(), Self::Error>;
+
+    /// Set the output drive strength
+    fn set_drive_strength(&mut self, strength: DriveStrength) -> Result<(), Self::Error>;
 }
 
 /// Output pin that can read its output state
 #[cfg(feature = "unproven")]
-pub trait StatefulOutputPin {
+pub trait StatefulOutputPin: OutputPin {
     /// Is the pin set to high?
-    fn is_set_high(&self) -> bool;
+    fn is_set_high(&self) -> Result<bool, Self::Error>;
 
     /// Is the pin set to low?
-    fn is_set_low(&self) -> bool;
+    fn is_set_low(&self) -> Result<bool, Self::Error>;
 }
 
 /// Output pin that can be toggled
@@ -26,16 +73,17 @@ pub trait StatefulOutputPin {
 /// [StatefulOutputPin](trait.StatefulOutputPin.html) are
 /// implemented. Otherwise, implement this using hardware mechanisms.
 #[cfg(feature = "unproven")]
-pub trait ToggleableOutputPin {
+pub trait ToggleableOutputPin: ErrorType {
     /// Toggle pin output.
-    fn toggle(&mut self);
+    fn toggle(&mut self) -> Result<(), Self::Error>;
 }
 
 /// If you can read **and** write the output state, a pin is
 /// toggleable by software.
 ///
 /// ```
-/// use embedded_hal::digital::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
+/// use core::convert::Infallible;
+/// use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin, ToggleableOutputPin};
 /// use embedded_hal::digital::toggleable;
 ///
 /// /// A virtual output pin that exists purely in software
@@ -43,32 +91,41 @@ pub trait ToggleableOutputPin {
 ///     state: bool
 /// }
 ///
+/// impl ErrorType for MyPin {
+///     type Error = Infallible;
+/// }
+///
 /// impl OutputPin for MyPin {
-///    fn set_low(&mut self) {
+///    fn set_low(&mut self) -> Result<(), Self::Error> {
 ///        self.state = false;
+///        Ok(())
 ///    }
-///    fn set_high(&mut self) {
+///    fn set_high(&mut self) -> Result<(), Self::Error> {
 ///        self.state = true;
+///        Ok(())
 ///    }
 /// }
 ///
 /// impl StatefulOutputPin for MyPin {
-///    fn is_set_low(&self) -> bool {
-///        !self.state
+///    fn is_set_low(&self) -> Result<bool, Self::Error> {
+///        Ok(!self.state)
 ///    }
-///    fn is_set_high(&self) -> bool {
-///        self.state
+///    fn is_set_high(&self) -> Result<bool, Self::Error> {
+///        Ok(self.state)
 ///    }
 /// }
 ///
 /// /// Opt-in to the software implementation.
 /// impl toggleable::Default for MyPin {}
 ///
+/// # fn main() -> Result<(), Infallible> {
 /// let mut pin = MyPin { state: false };
-/// pin.toggle();
-/// assert!(pin.is_set_high());
-/// pin.toggle();
-/// assert!(pin.is_set_low());
+/// pin.toggle()?;
+/// assert!(pin.is_set_high()?);
+/// pin.toggle()?;
+/// assert!(pin.is_set_low()?);
+/// # Ok(())
+/// # }
 /// ```
 #[cfg(feature = "unproven")]
 pub mod toggleable {
@@ -82,11 +139,11 @@ pub mod toggleable {
         P: Default,
     {
         /// Toggle pin output
-        fn toggle(&mut self) {
-            if self.is_set_low() {
-                self.set_high();
+        fn toggle(&mut self) -> Result<(), Self::Error> {
+            if self.is_set_low()? {
+                self.set_high()
             } else {
-                self.set_low();
+                self.set_low()
             }
         }
     }
@@ -116,29 +173,36 @@ impl<P: OutputPin> CachedOutputPin<P> {
     }
 }
 
+#[cfg(feature = "unproven")]
+impl<P: OutputPin> ErrorType for CachedOutputPin<P> {
+    type Error = P::Error;
+}
+
 /// Set output state and cache it
 #[cfg(feature = "unproven")]
 impl<P: OutputPin> OutputPin for CachedOutputPin<P> {
-    fn set_high(&mut self) {
-        self.pin.set_high();
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_high()?;
         self.state = true;
+        Ok(())
     }
 
-    fn set_low(&mut self) {
-        self.pin.set_low();
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_low()?;
         self.state = false;
+        Ok(())
     }
 }
 
 /// Obtain cached state
 #[cfg(feature = "unproven")]
 impl<P: OutputPin> StatefulOutputPin for CachedOutputPin<P> {
-    fn is_set_low(&self) -> bool {
-        !self.state
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.state)
     }
 
-    fn is_set_high(&self) -> bool {
-        self.state
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.state)
     }
 }
 
@@ -146,13 +210,150 @@ impl<P: OutputPin> StatefulOutputPin for CachedOutputPin<P> {
 #[cfg(feature = "unproven")]
 impl<P: OutputPin> toggleable::Default for CachedOutputPin<P> {}
 
+/// Forward drive mode and strength configuration to the inner pin
+#[cfg(feature = "unproven")]
+impl<P: DrivePin> DrivePin for CachedOutputPin<P> {
+    fn set_drive_mode(&mut self, mode: DriveMode) -> Result<(), Self::Error> {
+        self.pin.set_drive_mode(mode)
+    }
+
+    fn set_drive_strength(&mut self, strength: DriveStrength) -> Result<(), Self::Error> {
+        self.pin.set_drive_strength(strength)
+    }
+}
 
 /// Single digital input pin
 #[cfg(feature = "unproven")]
-pub trait InputPin {
+pub trait InputPin: ErrorType {
     /// Is the input pin high?
-    fn is_high(&self) -> bool;
+    fn is_high(&self) -> Result<bool, Self::Error>;
 
     /// Is the input pin low?
-    fn is_low(&self) -> bool;
+    fn is_low(&self) -> Result<bool, Self::Error>;
+}
+
+/// Internal pull resistor configuration for an input pin
+#[cfg(feature = "unproven")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pull {
+    /// No internal pull resistor
+    None,
+    /// Internal pull-up resistor enabled
+    Up,
+    /// Internal pull-down resistor enabled
+    Down,
+}
+
+/// Input pin whose internal pull resistor can be configured
+///
+/// This lets drivers that need a bus-held line (I2C recovery, button
+/// matrices) request the appropriate termination portably instead of
+/// reaching into vendor HAL code. Implementations that cannot honor a
+/// requested [`Pull`] mode (e.g. a pin with no pull-down capability)
+/// must return an error rather than silently falling back to a
+/// different mode.
+#[cfg(feature = "unproven")]
+pub trait PullPin: InputPin {
+    /// Set the internal pull resistor configuration
+    fn set_pull(&mut self, pull: Pull) -> Result<(), Self::Error>;
+}
+
+/// Single pin that can be switched between input and output mode
+///
+/// This models a "flex" pin whose direction is not fixed at
+/// compile-time: many peripherals (1-Wire, bit-banged I2C/SDIO,
+/// bidirectional data buses) need a single pin to flip between input
+/// and output during operation. Implementing this lets bit-bang
+/// driver crates be written generically instead of per-HAL.
+///
+/// ```
+/// use core::convert::Infallible;
+/// use embedded_hal::digital::{ErrorType, InputPin, IoPin, OutputPin, Pull};
+///
+/// /// A software pin that can act as either an input or an output
+/// struct FlexPin {
+///     high: bool,
+/// }
+///
+/// /// The same pin, currently configured as an input
+/// struct FlexInput(FlexPin);
+///
+/// /// The same pin, currently configured as an output
+/// struct FlexOutput(FlexPin);
+///
+/// impl ErrorType for FlexPin {
+///     type Error = Infallible;
+/// }
+/// impl ErrorType for FlexInput {
+///     type Error = Infallible;
+/// }
+/// impl ErrorType for FlexOutput {
+///     type Error = Infallible;
+/// }
+///
+/// impl InputPin for FlexInput {
+///     fn is_high(&self) -> Result<bool, Self::Error> {
+///         Ok(self.0.high)
+///     }
+///     fn is_low(&self) -> Result<bool, Self::Error> {
+///         Ok(!self.0.high)
+///     }
+/// }
+///
+/// impl OutputPin for FlexOutput {
+///     fn set_high(&mut self) -> Result<(), Self::Error> {
+///         self.0.high = true;
+///         Ok(())
+///     }
+///     fn set_low(&mut self) -> Result<(), Self::Error> {
+///         self.0.high = false;
+///         Ok(())
+///     }
+/// }
+///
+/// impl IoPin for FlexPin {
+///     type Input = FlexInput;
+///     type Output = FlexOutput;
+///
+///     fn into_input_pin(self, _pull: Pull) -> Result<Self::Input, Self::Error> {
+///         Ok(FlexInput(self))
+///     }
+///     fn into_output_pin(self, initial: bool) -> Result<Self::Output, Self::Error> {
+///         Ok(FlexOutput(FlexPin { high: initial }))
+///     }
+/// }
+///
+/// impl IoPin for FlexOutput {
+///     type Input = FlexInput;
+///     type Output = FlexOutput;
+///
+///     fn into_input_pin(self, _pull: Pull) -> Result<Self::Input, Self::Error> {
+///         Ok(FlexInput(self.0))
+///     }
+///     fn into_output_pin(self, initial: bool) -> Result<Self::Output, Self::Error> {
+///         Ok(FlexOutput(FlexPin { high: initial }))
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Infallible> {
+/// let pin = FlexPin { high: false };
+/// let mut output = pin.into_output_pin(true)?;
+/// output.set_low()?;
+/// let input = output.into_input_pin(Pull::None)?;
+/// assert!(input.is_low()?);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "unproven")]
+pub trait IoPin: ErrorType {
+    /// Input pin type produced by [`into_input_pin`](IoPin::into_input_pin)
+    type Input: InputPin;
+    /// Output pin type produced by [`into_output_pin`](IoPin::into_output_pin)
+    type Output: OutputPin;
+
+    /// Convert this pin into an input pin, configuring its pull resistor
+    fn into_input_pin(self, pull: Pull) -> Result<Self::Input, Self::Error>;
+
+    /// Convert this pin into an output pin, driving `initial` immediately
+    fn into_output_pin(self, initial: bool) -> Result<Self::Output, Self::Error>;
 }